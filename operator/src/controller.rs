@@ -1,7 +1,12 @@
 use futures::StreamExt;
 use kube::{
     api::ListParams,
-    runtime::{controller::Action, watcher::Config as WatcherConfig, Controller},
+    runtime::{
+        controller::Action,
+        finalizer::{finalizer, Event as FinalizerEvent},
+        watcher::Config as WatcherConfig,
+        Controller,
+    },
     Api, Client, CustomResource, CustomResourceExt, ResourceExt,
 };
 use schemars::JsonSchema;
@@ -10,8 +15,11 @@ use std::{sync::Arc, time::Duration};
 use tracing::{error, info, instrument};
 
 use crate::{
-    auth::handle_auth,
-    gateway::{handle_http_route, handle_http_route_key, handle_reference_grant},
+    auth::{handle_auth, remove_auth},
+    gateway::{
+        handle_http_route, handle_http_route_key, handle_reference_grant, remove_http_route,
+        remove_reference_grant,
+    },
     patch_resource_status, Error, Metrics, Network, Result, State,
 };
 
@@ -62,11 +70,25 @@ pub struct KupoPortStatus {
 }
 
 async fn reconcile(crd: Arc<KupoPort>, ctx: Arc<Context>) -> Result<Action> {
-    handle_reference_grant(&ctx.client, &crd).await?;
+    let namespace = crd.namespace().unwrap();
+    let kupo_ports = Api::<KupoPort>::namespaced(ctx.client.clone(), &namespace);
+
+    finalizer(&kupo_ports, KUPO_PORT_FINALIZER, crd, |event| async {
+        match event {
+            FinalizerEvent::Apply(crd) => apply(&crd, &ctx).await,
+            FinalizerEvent::Cleanup(crd) => cleanup(&crd, &ctx).await,
+        }
+    })
+    .await
+    .map_err(|err| Error::FinalizerError(Box::new(err)))
+}
+
+async fn apply(crd: &KupoPort, ctx: &Context) -> Result<Action> {
+    handle_reference_grant(&ctx.client, crd).await?;
 
-    let key = handle_auth(&ctx.client, &crd).await?;
-    let hostname = handle_http_route(&ctx.client, &crd).await?;
-    let hostname_key = handle_http_route_key(&ctx.client, &crd, &key).await?;
+    let key = handle_auth(&ctx.client, crd).await?;
+    let hostname = handle_http_route(&ctx.client, crd).await?;
+    let hostname_key = handle_http_route_key(&ctx.client, crd, &key).await?;
 
     let status = KupoPortStatus {
         endpoint_url: format!("https://{hostname}"),
@@ -91,6 +113,28 @@ async fn reconcile(crd: Arc<KupoPort>, ctx: Arc<Context>) -> Result<Action> {
     Ok(Action::await_change())
 }
 
+async fn cleanup(crd: &KupoPort, ctx: &Context) -> Result<Action> {
+    ignore_not_found(remove_reference_grant(&ctx.client, crd).await)?;
+    ignore_not_found(remove_http_route(&ctx.client, crd).await)?;
+    ignore_not_found(remove_auth(&ctx.client, crd).await)?;
+
+    info!(resource = crd.name_any(), "Cleanup completed");
+
+    Ok(Action::await_change())
+}
+
+// `finalizer()` only removes the finalizer once the cleanup closure returns
+// `Ok`, so a `remove_*` call erroring on a resource that's already gone (e.g.
+// a previous reconcile got partway through cleanup before being interrupted)
+// would otherwise leave the finalizer in place forever, since `error_policy`
+// just requeues and the same already-deleted resource fails again.
+fn ignore_not_found(result: Result<()>) -> Result<()> {
+    match result {
+        Err(Error::KubeError(kube::Error::Api(e))) if e.code == 404 => Ok(()),
+        other => other,
+    }
+}
+
 fn error_policy(crd: Arc<KupoPort>, err: &Error, ctx: Arc<Context>) -> Action {
     error!(error = err.to_string(), "reconcile failed");
     ctx.metrics.reconcile_failure(&crd, err);
@@ -17,6 +17,32 @@ pub struct Config {
     pub health_endpoint: String,
     pub health_poll_interval: std::time::Duration,
     pub private_endpoint: String,
+
+    // Response cache
+    pub cache_ttl: Duration,
+    pub cache_ttl_matches: Option<Duration>,
+    pub cache_ttl_datums: Option<Duration>,
+    pub cache_ttl_scripts: Option<Duration>,
+    pub cache_max_items: usize,
+    pub cache_lock_timeout: Duration,
+
+    // Response compression
+    pub compression_level: i32,
+    pub compression_min_size: usize,
+
+    // Upstream connection tuning
+    pub upstream_connect_timeout: Duration,
+    pub upstream_read_timeout: Duration,
+    pub upstream_write_timeout: Duration,
+    // Bounds connection establishment across retries (HttpPeer's
+    // `total_connection_timeout`), not the request as a whole.
+    pub upstream_total_connect_timeout: Duration,
+    // Deadline measured from the start of `request_filter`, checked each time
+    // a response body chunk is forwarded to the client. Time spent before the
+    // first response body chunk is not bounded by it, only by the per-phase
+    // upstream timeouts above.
+    pub request_timeout: Duration,
+    pub tcp_keepalive_interval: Duration,
 }
 impl Config {
     pub fn new() -> Self {
@@ -56,6 +82,120 @@ impl Config {
                 })
                 .unwrap_or(Duration::from_secs(10)),
             private_endpoint,
+            cache_ttl: env::var("CACHE_TTL")
+                .map(|v| {
+                    Duration::from_secs(
+                        v.parse::<u64>()
+                            .expect("CACHE_TTL must be a number in seconds. eg: 5"),
+                    )
+                })
+                .unwrap_or(Duration::from_secs(5)),
+            cache_ttl_matches: env::var("CACHE_TTL_MATCHES").ok().map(|v| {
+                Duration::from_secs(
+                    v.parse::<u64>()
+                        .expect("CACHE_TTL_MATCHES must be a number in seconds. eg: 5"),
+                )
+            }),
+            cache_ttl_datums: env::var("CACHE_TTL_DATUMS").ok().map(|v| {
+                Duration::from_secs(
+                    v.parse::<u64>()
+                        .expect("CACHE_TTL_DATUMS must be a number in seconds. eg: 5"),
+                )
+            }),
+            cache_ttl_scripts: env::var("CACHE_TTL_SCRIPTS").ok().map(|v| {
+                Duration::from_secs(
+                    v.parse::<u64>()
+                        .expect("CACHE_TTL_SCRIPTS must be a number in seconds. eg: 30"),
+                )
+            }),
+            cache_max_items: env::var("CACHE_MAX_ITEMS")
+                .map(|v| {
+                    v.parse::<usize>()
+                        .expect("CACHE_MAX_ITEMS must be a number. eg: 10000")
+                })
+                .unwrap_or(10_000),
+            cache_lock_timeout: env::var("CACHE_LOCK_TIMEOUT_MS")
+                .map(|v| {
+                    Duration::from_millis(
+                        v.parse::<u64>()
+                            .expect("CACHE_LOCK_TIMEOUT_MS must be a number in milliseconds. eg: 2000"),
+                    )
+                })
+                .unwrap_or(Duration::from_millis(2_000)),
+            compression_level: env::var("COMPRESSION_LEVEL")
+                .map(|v| {
+                    v.parse::<i32>()
+                        .expect("COMPRESSION_LEVEL must be a number. eg: 6")
+                })
+                .unwrap_or(6),
+            compression_min_size: env::var("COMPRESSION_MIN_SIZE")
+                .map(|v| {
+                    v.parse::<usize>()
+                        .expect("COMPRESSION_MIN_SIZE must be a number of bytes. eg: 256")
+                })
+                .unwrap_or(256),
+            upstream_connect_timeout: env::var("UPSTREAM_CONNECT_TIMEOUT")
+                .map(|v| {
+                    Duration::from_secs(
+                        v.parse::<u64>()
+                            .expect("UPSTREAM_CONNECT_TIMEOUT must be a number in seconds. eg: 10"),
+                    )
+                })
+                .unwrap_or(Duration::from_secs(10)),
+            upstream_read_timeout: env::var("UPSTREAM_READ_TIMEOUT")
+                .map(|v| {
+                    Duration::from_secs(
+                        v.parse::<u64>()
+                            .expect("UPSTREAM_READ_TIMEOUT must be a number in seconds. eg: 120"),
+                    )
+                })
+                .unwrap_or(Duration::from_secs(120)),
+            upstream_write_timeout: env::var("UPSTREAM_WRITE_TIMEOUT")
+                .map(|v| {
+                    Duration::from_secs(
+                        v.parse::<u64>()
+                            .expect("UPSTREAM_WRITE_TIMEOUT must be a number in seconds. eg: 120"),
+                    )
+                })
+                .unwrap_or(Duration::from_secs(120)),
+            upstream_total_connect_timeout: env::var("UPSTREAM_TOTAL_CONNECT_TIMEOUT")
+                .map(|v| {
+                    Duration::from_secs(v.parse::<u64>().expect(
+                        "UPSTREAM_TOTAL_CONNECT_TIMEOUT must be a number in seconds. eg: 10",
+                    ))
+                })
+                .unwrap_or(Duration::from_secs(10)),
+            request_timeout: env::var("UPSTREAM_REQUEST_TIMEOUT")
+                .map(|v| {
+                    Duration::from_secs(
+                        v.parse::<u64>()
+                            .expect("UPSTREAM_REQUEST_TIMEOUT must be a number in seconds. eg: 120"),
+                    )
+                })
+                .unwrap_or(Duration::from_secs(120)),
+            tcp_keepalive_interval: env::var("TCP_KEEPALIVE_SECS")
+                .map(|v| {
+                    Duration::from_secs(
+                        v.parse::<u64>()
+                            .expect("TCP_KEEPALIVE_SECS must be a number in seconds. eg: 7200"),
+                    )
+                })
+                .unwrap_or(Duration::from_secs(7_200)),
+        }
+    }
+
+    /// Per-route TTL for cached responses, falling back to `cache_ttl` for
+    /// routes without an override (or for anything that isn't one of the
+    /// cacheable Kupo endpoints).
+    pub fn cache_ttl_for_path(&self, path: &str) -> Duration {
+        if path.starts_with("/matches") {
+            self.cache_ttl_matches.unwrap_or(self.cache_ttl)
+        } else if path.starts_with("/datums") {
+            self.cache_ttl_datums.unwrap_or(self.cache_ttl)
+        } else if path.starts_with("/scripts") {
+            self.cache_ttl_scripts.unwrap_or(self.cache_ttl)
+        } else {
+            self.cache_ttl
         }
     }
 
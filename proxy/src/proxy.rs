@@ -1,14 +1,21 @@
 use async_trait::async_trait;
 use bytes::Bytes;
 use pingora::http::{ResponseHeader, StatusCode};
+use pingora::modules::http::{compression::ResponseCompressionBuilder, HttpModules};
+use pingora::protocols::l4::ext::TcpKeepalive;
 use pingora::Result;
 use pingora::{
     proxy::{ProxyHttp, Session},
     upstreams::peer::HttpPeer,
 };
-use pingora_limits::rate::Rate;
+use pingora_cache::{
+    eviction::simple_lru::Manager, lock::CacheLock, CacheKey, CacheMeta, MemCache, NoCacheReason,
+    RespCacheable,
+};
 use regex::Regex;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 use tracing::info;
 
 use crate::config::Config;
@@ -16,72 +23,158 @@ use crate::{Consumer, State, Tier};
 
 static DMTR_API_KEY: &str = "dmtr-api-key";
 
+/// A sliding-window rate counter. Rather than a single fixed-window `Rate`
+/// resetting to zero at each interval boundary (which lets a consumer burst
+/// up to 2x the limit across a boundary), this keeps the current window's
+/// count plus a time-weighted share of the previous window's count, so the
+/// limit is smoothed continuously rather than reset in steps.
+struct SlidingWindowCounter {
+    interval_ms: u64,
+    limit: u64,
+    epoch: Instant,
+    window_start_ms: AtomicU64,
+    previous: AtomicU64,
+    current: AtomicU64,
+}
+
+impl SlidingWindowCounter {
+    fn new(interval: Duration, limit: u64) -> Self {
+        Self {
+            interval_ms: interval.as_millis().max(1) as u64,
+            limit,
+            epoch: Instant::now(),
+            window_start_ms: AtomicU64::new(0),
+            previous: AtomicU64::new(0),
+            current: AtomicU64::new(0),
+        }
+    }
+
+    /// Records `weight` requests and returns whether the weighted sum of the
+    /// current and previous windows now exceeds the tier's limit.
+    fn observe(&self, weight: u64) -> bool {
+        let now_ms = self.epoch.elapsed().as_millis() as u64;
+
+        // Roll the window forward one interval at a time (or, once two or more
+        // intervals have passed, straight to `now_ms` with `previous` cleared),
+        // each step guarded by a CAS on `window_start_ms`: only the thread that wins the CAS is allowed
+        // to move `current` into `previous`, so two requests racing at the same
+        // boundary can't both swap `current` and have the second `store` clobber
+        // the first one's real count with a stale `0`. A thread that loses the
+        // CAS just reloads `window_start_ms` and re-evaluates instead of retrying
+        // the rollover itself.
+        loop {
+            let window_start = self.window_start_ms.load(Ordering::Acquire);
+            let elapsed = now_ms.saturating_sub(window_start);
+
+            if elapsed < self.interval_ms {
+                break;
+            }
+
+            let skipped_windows = elapsed >= self.interval_ms * 2;
+            let new_window_start = if skipped_windows {
+                now_ms
+            } else {
+                window_start + self.interval_ms
+            };
+
+            if self
+                .window_start_ms
+                .compare_exchange(
+                    window_start,
+                    new_window_start,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                )
+                .is_ok()
+            {
+                let rolled_over = self.current.swap(0, Ordering::AcqRel);
+                let carried = if skipped_windows { 0 } else { rolled_over };
+                self.previous.store(carried, Ordering::Release);
+            }
+        }
+
+        let current = self.current.fetch_add(weight, Ordering::AcqRel) + weight;
+        let window_start = self.window_start_ms.load(Ordering::Acquire);
+        let elapsed_in_window = now_ms.saturating_sub(window_start) as f64;
+        let overlap = (1.0 - elapsed_in_window / self.interval_ms as f64).clamp(0.0, 1.0);
+        let weighted = self.previous.load(Ordering::Acquire) as f64 * overlap + current as f64;
+
+        weighted > self.limit as f64
+    }
+}
+
 pub struct KupoProxy {
     state: Arc<State>,
     config: Arc<Config>,
     host_regex: Regex,
     private_endpoint_regex: Regex,
+    cache_backend: MemCache,
+    cache_eviction: Box<Manager>,
+    cache_lock: CacheLock,
 }
 impl KupoProxy {
     pub fn new(state: Arc<State>, config: Arc<Config>) -> Self {
         let host_regex = Regex::new(r"([dmtr_]?[\w\d-]+)?\.?.+").unwrap();
         let private_endpoint_regex = Regex::new(&config.private_endpoint).unwrap();
 
+        let cache_backend = MemCache::new();
+        let cache_eviction = Box::new(Manager::new(config.cache_max_items));
+        let cache_lock = CacheLock::new(config.cache_lock_timeout);
+
         Self {
             state,
             config,
             host_regex,
             private_endpoint_regex,
+            cache_backend,
+            cache_eviction,
+            cache_lock,
         }
     }
 
-    async fn has_limiter(&self, consumer: &Consumer) -> bool {
-        let rate_limiter_map = self.state.limiter.read().await;
-        rate_limiter_map.get(&consumer.key).is_some()
-    }
-
-    async fn add_limiter(&self, consumer: &Consumer, tier: &Tier) {
-        let rates = tier
-            .rates
-            .iter()
-            .map(|r| (r.clone(), Rate::new(r.interval)))
-            .collect();
-
-        self.state
-            .limiter
-            .write()
-            .await
-            .insert(consumer.key.clone(), rates);
+    fn is_cacheable_path(&self, path: &str) -> bool {
+        matches!(path, "/matches" | "/datums" | "/scripts")
+            || path.starts_with("/matches/")
+            || path.starts_with("/datums/")
+            || path.starts_with("/scripts/")
     }
 
     async fn limiter(&self, consumer: &Consumer) -> Result<bool> {
         let tiers = self.state.tiers.read().await.clone();
-        let tier = tiers.get(&consumer.tier);
-        if tier.is_none() {
+        let Some(tier) = tiers.get(&consumer.tier) else {
             return Ok(true);
-        }
-        let tier = tier.unwrap();
-
-        if !self.has_limiter(consumer).await {
-            self.add_limiter(consumer, tier).await;
-        }
-
-        let rate_limiter_map = self.state.limiter.read().await;
-        let rates = rate_limiter_map.get(&consumer.key).unwrap();
+        };
 
-        if rates
-            .iter()
-            .any(|(t, r)| r.observe(&consumer.key, 1) > t.limit)
+        // Fast path: the overwhelming majority of requests are from a consumer
+        // whose counters already exist, so only a read lock is needed.
         {
-            return Ok(true);
+            let limiter = self.state.limiter.read().await;
+            if let Some(rates) = limiter.get(&consumer.key) {
+                return Ok(rates.iter().any(|(_, counter)| counter.observe(1)));
+            }
         }
 
-        Ok(false)
+        // Slow path: take the write lock and use `entry()` to check-and-insert
+        // atomically under it, so two requests racing to create the same
+        // consumer's entry can't have the second clobber the first's counters.
+        let mut limiter = self.state.limiter.write().await;
+        let rates = limiter.entry(consumer.key.clone()).or_insert_with(|| {
+            tier.rates
+                .iter()
+                .map(|r| (r.clone(), SlidingWindowCounter::new(r.interval, r.limit)))
+                .collect()
+        });
+
+        Ok(rates.iter().any(|(_, counter)| counter.observe(1)))
     }
 
     async fn respond_health(&self, session: &mut Session, ctx: &mut Context) {
         ctx.is_health_request = true;
         session.set_keepalive(None);
+        // Health responses are written directly here and never reach
+        // `upstream_response_filter`, so the compression skip has to happen
+        // on this path instead.
+        session.downstream_compression.adjust_level(0);
 
         let is_healthy = *self.state.upstream_health.read().await;
         let (code, message) = if is_healthy {
@@ -118,6 +211,10 @@ pub struct Context {
     is_health_request: bool,
     instance: String,
     consumer: Consumer,
+    // Set once at the start of `request_filter`; `response_body_filter`
+    // compares it against `Config::request_timeout` on every body chunk, since
+    // `HttpPeer`'s timeouts only bound individual connect/read/write phases.
+    started_at: Option<Instant>,
 }
 
 #[async_trait]
@@ -127,11 +224,18 @@ impl ProxyHttp for KupoProxy {
         Context::default()
     }
 
+    fn init_downstream_modules(&self, modules: &mut HttpModules) {
+        modules.add_module(ResponseCompressionBuilder::enable(
+            self.config.compression_level,
+        ));
+    }
+
     async fn request_filter(&self, session: &mut Session, ctx: &mut Self::CTX) -> Result<bool>
     where
         Self::CTX: Send + Sync,
     {
         let state = self.state.clone();
+        ctx.started_at = Some(Instant::now());
 
         // Check if the request is going to the health endpoint before continuing.
         let path = session.req_header().uri.path();
@@ -179,15 +283,127 @@ impl ProxyHttp for KupoProxy {
         Ok(false)
     }
 
+    // With the default `KUPO_PRIVATE_ENDPOINT_REGEX`, `PUT /patterns(/...)` is
+    // matched by `private_endpoint_regex` and rejected with 401 in
+    // `request_filter` before it reaches upstream. Cached entries are never
+    // purged on writes; cache freshness is bounded by
+    // `Config::cache_ttl_for_path` alone.
+    fn request_cache_filter(&self, session: &mut Session, ctx: &mut Self::CTX) -> Result<()> {
+        let req_header = session.req_header();
+        if req_header.method == "GET" && self.is_cacheable_path(req_header.uri.path()) {
+            session.cache.enable(
+                &self.cache_backend,
+                Some(self.cache_eviction.as_ref()),
+                None,
+                Some(&self.cache_lock),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn cache_key_callback(&self, session: &Session, ctx: &mut Self::CTX) -> Result<CacheKey> {
+        let req_header = session.req_header();
+        let mut key = CacheKey::default(req_header);
+        key.set_variance_key(format!("{}:{}", ctx.consumer.network, ctx.consumer.pruned));
+
+        Ok(key)
+    }
+
+    fn response_cache_filter(
+        &self,
+        session: &Session,
+        resp: &ResponseHeader,
+        _ctx: &mut Self::CTX,
+    ) -> Result<RespCacheable> {
+        if !resp.status.is_success() {
+            return Ok(RespCacheable::Uncacheable(NoCacheReason::OriginNotCache));
+        }
+
+        let path = session.req_header().uri.path();
+        let fresh_for = self.config.cache_ttl_for_path(path);
+
+        let meta = CacheMeta::new(SystemTime::now() + fresh_for, SystemTime::now(), 1, 1, resp.clone());
+
+        Ok(RespCacheable::Cacheable(meta))
+    }
+
     async fn upstream_peer(
         &self,
         _session: &mut Session,
         ctx: &mut Self::CTX,
     ) -> Result<Box<HttpPeer>> {
-        let http_peer = HttpPeer::new(&ctx.instance, false, String::default());
+        let mut http_peer = HttpPeer::new(&ctx.instance, false, String::default());
+
+        let options = &mut http_peer.options;
+        options.connection_timeout = Some(self.config.upstream_connect_timeout);
+        options.read_timeout = Some(self.config.upstream_read_timeout);
+        options.write_timeout = Some(self.config.upstream_write_timeout);
+        // Bounds connection establishment across retries, not the request as a
+        // whole - the overall deadline is enforced separately in
+        // `response_body_filter` via `Config::request_timeout`.
+        options.total_connection_timeout = Some(self.config.upstream_total_connect_timeout);
+        options.tcp_keepalive = Some(TcpKeepalive {
+            idle: self.config.tcp_keepalive_interval,
+            interval: self.config.tcp_keepalive_interval,
+            count: 3,
+        });
+
         Ok(Box::new(http_peer))
     }
 
+    fn response_body_filter(
+        &self,
+        _session: &mut Session,
+        _body: &mut Option<Bytes>,
+        _end_of_stream: bool,
+        ctx: &mut Self::CTX,
+    ) -> Result<Option<Duration>> {
+        let elapsed = ctx.started_at.map(|t| t.elapsed()).unwrap_or_default();
+        if elapsed > self.config.request_timeout {
+            return Err(pingora::Error::new(pingora::ErrorType::ReadTimedout));
+        }
+
+        Ok(None)
+    }
+
+    fn upstream_response_filter(
+        &self,
+        session: &mut Session,
+        upstream_response: &mut ResponseHeader,
+        _ctx: &mut Self::CTX,
+    ) {
+        let already_compressed = upstream_response.headers.contains_key("content-encoding");
+        let content_type = upstream_response
+            .headers
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+        let non_compressible = content_type.starts_with("image/")
+            || content_type.starts_with("video/")
+            || content_type.starts_with("audio/");
+
+        if already_compressed || non_compressible {
+            session.downstream_compression.adjust_level(0);
+            return;
+        }
+
+        // A response with no `Content-Length` (e.g. chunked transfer encoding)
+        // has an unknown size rather than a known-small one - that's exactly
+        // the large, streamed payload shape this feature targets, so only
+        // disable compression when the length is known to be under the
+        // threshold, not when it's simply absent.
+        let content_length = upstream_response
+            .headers
+            .get("content-length")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<usize>().ok());
+
+        if matches!(content_length, Some(len) if len < self.config.compression_min_size) {
+            session.downstream_compression.adjust_level(0);
+        }
+    }
+
     async fn logging(
         &self,
         session: &mut Session,